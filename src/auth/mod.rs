@@ -0,0 +1,144 @@
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, SaltString};
+use argon2::{Argon2, PasswordHasher, PasswordVerifier};
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header, request::Parts},
+};
+use chrono::Utc;
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use sea_orm::{prelude::Uuid, ColumnTrait, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+
+use crate::config::AppConfig;
+use crate::controller::Context;
+use crate::error::AppError;
+use crate::model::sessions::{self, Entity as Sessions};
+use crate::model::users::{Entity as Users, Model as User};
+
+/// Claims embedded in the signed JWT.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Claims {
+    /// Subject — the user id the token was issued for.
+    pub sub: String,
+    /// Expiry, seconds since the Unix epoch.
+    pub exp: usize,
+}
+
+/// Hash a plaintext password with Argon2 for storage.
+pub fn hash_password(password: &str) -> Result<String, AppError> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// Verify a plaintext password against a stored Argon2 hash.
+pub fn verify_password(password: &str, hash: &str) -> bool {
+    PasswordHash::new(hash)
+        .map(|parsed| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+/// Issue a signed JWT for the given user, expiring after `jwt_expiry_seconds`.
+pub fn issue_token(user_id: Uuid, config: &AppConfig) -> Result<String, AppError> {
+    let exp = Utc::now().timestamp() + config.jwt_expiry_seconds;
+    let claims = Claims {
+        sub: user_id.to_string(),
+        exp: exp as usize,
+    };
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(config.jwt_secret.as_bytes()),
+    )
+    .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// Decode and validate a JWT, returning its claims.
+pub fn decode_token(token: &str, config: &AppConfig) -> Result<Claims, AppError> {
+    decode::<Claims>(
+        token,
+        &DecodingKey::from_secret(config.jwt_secret.as_bytes()),
+        &Validation::default(),
+    )
+    .map(|data| data.claims)
+    .map_err(|_| AppError::Unauthorized("Invalid or expired token".to_owned()))
+}
+
+/// An authenticated user, produced by extracting and validating the bearer
+/// token (or `session` cookie) from the request and loading the owner.
+///
+/// Carries the presented `token` so mutating endpoints (e.g. logout) can act on
+/// the exact session that authenticated the request.
+pub struct AuthUser {
+    pub user: User,
+    pub token: String,
+}
+
+#[async_trait]
+impl FromRequestParts<Context> for AuthUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, ctx: &Context) -> Result<Self, Self::Rejection> {
+        let token = bearer_token(parts)
+            .or_else(|| session_cookie(parts))
+            .ok_or_else(|| AppError::Unauthorized("Missing credentials".to_owned()))?;
+
+        let claims = decode_token(&token, &ctx.config)?;
+        let user_id: Uuid = claims
+            .sub
+            .parse()
+            .map_err(|_| AppError::Unauthorized("Invalid token subject".to_owned()))?;
+
+        // A valid signature is not enough: the session must still exist and not
+        // have expired, so logout (which deletes the row) actually revokes the
+        // token instead of leaving it live until JWT expiry.
+        let session = Sessions::find()
+            .filter(sessions::Column::Token.eq(token.clone()))
+            .one(ctx.db())
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("Session has been revoked".to_owned()))?;
+
+        if session.expires_at.with_timezone(&Utc) < Utc::now() {
+            return Err(AppError::Unauthorized("Session has expired".to_owned()));
+        }
+
+        let user = Users::find_by_id(user_id)
+            .one(ctx.db())
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("User no longer exists".to_owned()))?;
+
+        Ok(AuthUser { user, token })
+    }
+}
+
+/// Pull the token out of an `Authorization: Bearer <token>` header.
+fn bearer_token(parts: &Parts) -> Option<String> {
+    parts
+        .headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| token.trim().to_owned())
+}
+
+/// Pull the token out of a `session=<token>` cookie.
+fn session_cookie(parts: &Parts) -> Option<String> {
+    parts
+        .headers
+        .get(header::COOKIE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|cookies| {
+            cookies
+                .split(';')
+                .filter_map(|cookie| cookie.trim().split_once('='))
+                .find(|(name, _)| *name == "session")
+                .map(|(_, token)| token.to_owned())
+        })
+}
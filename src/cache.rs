@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+use sea_orm::prelude::Uuid;
+
+use crate::schema::NoteResponse;
+
+/// A cached note together with the owner it belongs to and the instant it was
+/// fetched from the database.
+struct CachedNote {
+    owner_id: Uuid,
+    note: NoteResponse,
+    fetched_at: SystemTime,
+}
+
+/// Read-through, TTL-bounded cache for single-note lookups, modeled on the
+/// node-cache pattern. Entries older than `ttl` are treated as misses and any
+/// mutation path must invalidate its key before returning.
+#[derive(Clone)]
+pub struct NoteCache {
+    entries: Arc<RwLock<HashMap<Uuid, CachedNote>>>,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
+    ttl: Duration,
+}
+
+impl NoteCache {
+    pub fn new(ttl: Duration) -> NoteCache {
+        NoteCache {
+            entries: Arc::new(RwLock::new(HashMap::new())),
+            hits: Arc::new(AtomicU64::new(0)),
+            misses: Arc::new(AtomicU64::new(0)),
+            ttl,
+        }
+    }
+
+    /// Return a live (non-expired) cached note owned by `owner_id`, counting the
+    /// hit or miss. An entry belonging to another owner is treated as a miss so
+    /// the cache never serves one user's note to another.
+    pub fn get(&self, id: &Uuid, owner_id: Uuid) -> Option<NoteResponse> {
+        let entries = self.entries.read().expect("note cache poisoned");
+        if let Some(entry) = entries.get(id) {
+            let fresh = entry
+                .fetched_at
+                .elapsed()
+                .map(|age| age < self.ttl)
+                .unwrap_or(false);
+            if fresh && entry.owner_id == owner_id {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Some(entry.note.clone());
+            }
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        None
+    }
+
+    /// Insert or refresh the cached note for `id`, stamped as fetched now.
+    pub fn insert(&self, id: Uuid, owner_id: Uuid, note: NoteResponse) {
+        let mut entries = self.entries.write().expect("note cache poisoned");
+        entries.insert(
+            id,
+            CachedNote {
+                owner_id,
+                note,
+                fetched_at: SystemTime::now(),
+            },
+        );
+    }
+
+    /// Drop the cached entry for `id`, if any.
+    pub fn invalidate(&self, id: &Uuid) {
+        let mut entries = self.entries.write().expect("note cache poisoned");
+        entries.remove(id);
+    }
+
+    /// Current `(hits, misses)` counters.
+    pub fn stats(&self) -> (u64, u64) {
+        (
+            self.hits.load(Ordering::Relaxed),
+            self.misses.load(Ordering::Relaxed),
+        )
+    }
+}
@@ -0,0 +1,149 @@
+use std::net::{SocketAddr, ToSocketAddrs};
+
+use serde::Deserialize;
+
+/// Runtime configuration for the service.
+///
+/// Values are resolved from environment variables, optionally seeded from a
+/// `config.toml` in the working directory, so operators can change the listen
+/// port or advertise an external base domain without recompiling.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AppConfig {
+    /// Host/interface the server binds to (e.g. `0.0.0.0`).
+    #[serde(default = "default_host")]
+    pub host: String,
+    /// TCP port the server listens on.
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// Public base URL/domain the service is reachable at, used when building
+    /// absolute links for clients.
+    #[serde(default = "default_base_url")]
+    pub base_url: String,
+    /// Default page size applied when a request omits `limit`.
+    #[serde(default = "default_page_limit")]
+    pub default_page_limit: u64,
+    /// Secret used to sign and verify JWTs.
+    #[serde(default = "default_jwt_secret")]
+    pub jwt_secret: String,
+    /// Lifetime of an issued JWT, in seconds.
+    #[serde(default = "default_jwt_expiry")]
+    pub jwt_expiry_seconds: i64,
+    /// `Max-Age` of the session cookie, in seconds.
+    #[serde(default = "default_session_max_age")]
+    pub session_max_age_seconds: i64,
+    /// Time-to-live of single-note cache entries, in seconds.
+    #[serde(default = "default_cache_ttl")]
+    pub cache_ttl_seconds: u64,
+}
+
+fn default_host() -> String {
+    "0.0.0.0".to_owned()
+}
+
+fn default_port() -> u16 {
+    8000
+}
+
+fn default_base_url() -> String {
+    "http://localhost:8000".to_owned()
+}
+
+fn default_page_limit() -> u64 {
+    10
+}
+
+fn default_jwt_secret() -> String {
+    "change-me-in-production".to_owned()
+}
+
+fn default_jwt_expiry() -> i64 {
+    // 1 hour
+    3600
+}
+
+fn default_session_max_age() -> i64 {
+    // 7 days
+    604_800
+}
+
+fn default_cache_ttl() -> u64 {
+    // 30 seconds
+    30
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        AppConfig {
+            host: default_host(),
+            port: default_port(),
+            base_url: default_base_url(),
+            default_page_limit: default_page_limit(),
+            jwt_secret: default_jwt_secret(),
+            jwt_expiry_seconds: default_jwt_expiry(),
+            session_max_age_seconds: default_session_max_age(),
+            cache_ttl_seconds: default_cache_ttl(),
+        }
+    }
+}
+
+impl AppConfig {
+    /// Load configuration, layering environment variables on top of an optional
+    /// `config.toml`. Missing values fall back to the defaults above.
+    pub fn from_env() -> AppConfig {
+        let mut config = std::fs::read_to_string("config.toml")
+            .ok()
+            .and_then(|raw| toml::from_str::<AppConfig>(&raw).ok())
+            .unwrap_or_default();
+
+        if let Ok(host) = std::env::var("HOST") {
+            config.host = host;
+        }
+        if let Some(port) = std::env::var("PORT").ok().and_then(|p| p.parse().ok()) {
+            config.port = port;
+        }
+        if let Ok(base_url) = std::env::var("BASE_URL") {
+            config.base_url = base_url;
+        }
+        if let Some(limit) = std::env::var("DEFAULT_PAGE_LIMIT")
+            .ok()
+            .and_then(|l| l.parse().ok())
+        {
+            config.default_page_limit = limit;
+        }
+        if let Ok(secret) = std::env::var("JWT_SECRET") {
+            config.jwt_secret = secret;
+        }
+        if let Some(expiry) = std::env::var("JWT_EXPIRY_SECONDS")
+            .ok()
+            .and_then(|e| e.parse().ok())
+        {
+            config.jwt_expiry_seconds = expiry;
+        }
+        if let Some(max_age) = std::env::var("SESSION_MAX_AGE_SECONDS")
+            .ok()
+            .and_then(|m| m.parse().ok())
+        {
+            config.session_max_age_seconds = max_age;
+        }
+        if let Some(ttl) = std::env::var("CACHE_TTL_SECONDS")
+            .ok()
+            .and_then(|t| t.parse().ok())
+        {
+            config.cache_ttl_seconds = ttl;
+        }
+
+        config
+    }
+
+    /// The socket address the server should bind to.
+    ///
+    /// Resolves `host` through the OS resolver so hostnames such as
+    /// `localhost` work, not just numeric IP literals.
+    pub fn socket_addr(&self) -> SocketAddr {
+        (self.host.as_str(), self.port)
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addrs| addrs.next())
+            .expect("HOST/PORT must resolve to a socket address")
+    }
+}
@@ -0,0 +1,126 @@
+use axum::{
+    extract::State,
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use chrono::{Duration, Utc};
+use sea_orm::{
+    prelude::Uuid, ActiveModelTrait, ActiveValue::NotSet, ColumnTrait, EntityTrait, QueryFilter, Set,
+};
+use serde_json::json;
+
+use crate::auth::{hash_password, issue_token, verify_password, AuthUser};
+use crate::controller::Context;
+use crate::error::{AppError, AppResult};
+use crate::model::sessions::{self, Entity as Sessions};
+use crate::model::users::{self, Entity as Users};
+use crate::schema::{LoginSchema, RegisterSchema};
+
+/// Persist a session row for the issued token and render the `Set-Cookie`
+/// header so the browser stores it for `session_max_age_seconds`.
+async fn start_session(ctx: &Context, user_id: Uuid, token: &str) -> AppResult<HeaderMap> {
+    let expires_at = Utc::now() + Duration::seconds(ctx.config.session_max_age_seconds);
+
+    let session = sessions::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id),
+        token: Set(token.to_owned()),
+        expires_at: Set(expires_at.into()),
+        created_at: NotSet,
+    };
+    session.insert(ctx.db()).await?;
+
+    let cookie = format!(
+        "session={}; HttpOnly; Path=/; SameSite=Lax; Max-Age={}",
+        token, ctx.config.session_max_age_seconds
+    );
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::SET_COOKIE,
+        cookie
+            .parse()
+            .map_err(|_| AppError::Internal("Failed to build session cookie".to_owned()))?,
+    );
+    Ok(headers)
+}
+
+pub async fn register_handler(
+    State(ctx): State<Context>,
+    Json(data): Json<RegisterSchema>,
+) -> AppResult<impl IntoResponse> {
+    let password_hash = hash_password(&data.password)?;
+
+    let new_user = users::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        email: Set(data.email.clone()),
+        password_hash: Set(password_hash),
+        created_at: NotSet,
+        updated_at: NotSet,
+    };
+    let user = new_user.insert(ctx.db()).await?;
+
+    let token = issue_token(user.id, &ctx.config)?;
+    let headers = start_session(&ctx, user.id, &token).await?;
+
+    let body = json!({
+        "status": "success",
+        "data": {
+            "token": token,
+            "user": { "id": user.id, "email": user.email }
+        }
+    });
+    Ok((StatusCode::CREATED, headers, Json(body)))
+}
+
+pub async fn login_handler(
+    State(ctx): State<Context>,
+    Json(data): Json<LoginSchema>,
+) -> AppResult<impl IntoResponse> {
+    let user = Users::find()
+        .filter(users::Column::Email.eq(data.email.clone()))
+        .one(ctx.db())
+        .await?
+        .ok_or_else(|| AppError::Unauthorized("Invalid email or password".to_owned()))?;
+
+    if !verify_password(&data.password, &user.password_hash) {
+        return Err(AppError::Unauthorized(
+            "Invalid email or password".to_owned(),
+        ));
+    }
+
+    let token = issue_token(user.id, &ctx.config)?;
+    let headers = start_session(&ctx, user.id, &token).await?;
+
+    let body = json!({
+        "status": "success",
+        "data": {
+            "token": token,
+            "user": { "id": user.id, "email": user.email }
+        }
+    });
+    Ok((StatusCode::OK, headers, Json(body)))
+}
+
+pub async fn logout_handler(
+    State(ctx): State<Context>,
+    auth: AuthUser,
+) -> AppResult<impl IntoResponse> {
+    // Revoke only the session that authenticated this request, leaving the
+    // user's other sessions (e.g. another device) intact.
+    Sessions::delete_many()
+        .filter(sessions::Column::Token.eq(auth.token))
+        .exec(ctx.db())
+        .await?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::SET_COOKIE,
+        "session=; HttpOnly; Path=/; SameSite=Lax; Max-Age=0"
+            .parse()
+            .map_err(|_| AppError::Internal("Failed to clear session cookie".to_owned()))?,
+    );
+
+    let body = json!({ "status": "success", "message": "Logged out" });
+    Ok((StatusCode::OK, headers, Json(body)))
+}
@@ -1,30 +1,101 @@
 use axum::{
-    extract::FromRef,
-    routing::{post, put},
+    routing::{get, post, put},
     Router,
 };
+use std::time::Duration;
+
 use sea_orm::DatabaseConnection;
+use tokio::sync::broadcast;
+
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::cache::NoteCache;
+use crate::config::AppConfig;
+use crate::event::{NoteEvent, EVENT_CHANNEL_CAPACITY};
+use crate::schema::{CreateNoteSchema, NoteResponse, UpdateNoteSchema};
 
+use self::auth_handler::{login_handler, logout_handler, register_handler};
 use self::route_handler::{
-    create_handler, delete_handler, find_all_handler, find_by_id_handler, update_handler,
+    cache_stats_handler, create_handler, delete_handler, events_handler, find_all_handler,
+    find_by_id_handler, update_handler,
 };
 
+mod auth_handler;
 mod route_handler;
 
-#[derive(FromRef, Clone)]
-pub struct AppState {
-    pub database: DatabaseConnection,
+/// Shared application context handed to every handler via `State`.
+///
+/// Bundles the database handle together with the loaded [`AppConfig`] so
+/// handlers can reach runtime settings (page limits, external base URL) without
+/// re-reading the environment.
+#[derive(Clone)]
+pub struct Context {
+    database: DatabaseConnection,
+    pub config: AppConfig,
+    events: broadcast::Sender<NoteEvent>,
+    cache: NoteCache,
+}
+
+impl Context {
+    pub fn new(database: DatabaseConnection, config: AppConfig) -> Context {
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        let cache = NoteCache::new(Duration::from_secs(config.cache_ttl_seconds));
+        Context {
+            database,
+            config,
+            events,
+            cache,
+        }
+    }
+
+    /// Borrow the underlying database connection.
+    pub fn db(&self) -> &DatabaseConnection {
+        &self.database
+    }
+
+    /// The read-through cache for single-note lookups.
+    pub fn cache(&self) -> &NoteCache {
+        &self.cache
+    }
+
+    /// The broadcast sender used to publish [`NoteEvent`]s to SSE clients.
+    pub fn events(&self) -> &broadcast::Sender<NoteEvent> {
+        &self.events
+    }
 }
 
-pub async fn create_routes(database: DatabaseConnection) -> Router {
-    let app_state = AppState { database };
+/// Machine-readable OpenAPI description of the notes API.
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        route_handler::find_all_handler,
+        route_handler::find_by_id_handler,
+        route_handler::create_handler,
+        route_handler::update_handler,
+        route_handler::delete_handler,
+    ),
+    components(schemas(CreateNoteSchema, UpdateNoteSchema, NoteResponse)),
+    tags((name = "notes", description = "Note CRUD operations"))
+)]
+pub struct ApiDoc;
+
+pub async fn create_routes(ctx: Context) -> Router {
     Router::new()
+        .merge(
+            SwaggerUi::new("/docs").url("/api-docs/openapi.json", ApiDoc::openapi()),
+        )
+        .route("/api/auth/register", post(register_handler))
+        .route("/api/auth/login", post(login_handler))
+        .route("/api/auth/logout", post(logout_handler))
         .route("/api/notes", post(create_handler).get(find_all_handler))
+        .route("/api/notes/events", get(events_handler))
+        .route("/api/notes/cache/stats", get(cache_stats_handler))
         .route(
             "/api/notes/:id",
             put(update_handler)
                 .get(find_by_id_handler)
                 .delete(delete_handler),
         )
-        .with_state(app_state)
+        .with_state(ctx)
 }
@@ -1,35 +1,136 @@
+use std::convert::Infallible;
+
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::IntoResponse,
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse,
+    },
     Json,
 };
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, FixedOffset};
+use futures::Stream;
 use sea_orm::{
-    prelude::Uuid, ActiveModelTrait, ActiveValue::NotSet, DatabaseConnection, EntityTrait, Set,
+    prelude::Uuid, sea_query::Expr, ActiveModelTrait, ActiveValue::NotSet, ColumnTrait, Condition,
+    EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
 };
-use serde_json::{json, Value};
+use serde_json::json;
+use tokio_stream::{wrappers::BroadcastStream, StreamExt};
 
+use crate::auth::AuthUser;
+use crate::controller::Context;
+use crate::error::{AppError, AppResult};
+use crate::event::NoteEvent;
 use crate::{
     model::notes,
     schema::{CreateNoteSchema, FilterOptions, UpdateNoteSchema},
 };
 use crate::{model::notes::Entity as Notes, schema::NoteResponse};
 
+/// A keyset cursor pointing at the `(created_at, id)` of the last row a client
+/// has already seen. Encoded as the base64 of `"<created_at rfc3339>|<id>"`.
+struct Cursor {
+    created_at: DateTime<FixedOffset>,
+    id: Uuid,
+}
+
+/// The Unix epoch as a fixed-offset timestamp, used as the cursor fallback for
+/// rows whose `created_at` is null so pagination never strands a client.
+fn epoch() -> DateTime<FixedOffset> {
+    DateTime::from_timestamp(0, 0)
+        .expect("epoch is a valid timestamp")
+        .fixed_offset()
+}
+
+impl Cursor {
+    fn encode(created_at: DateTime<FixedOffset>, id: Uuid) -> String {
+        STANDARD.encode(format!("{}|{}", created_at.to_rfc3339(), id))
+    }
+
+    fn decode(raw: &str) -> Option<Cursor> {
+        let bytes = STANDARD.decode(raw).ok()?;
+        let text = String::from_utf8(bytes).ok()?;
+        let (created_at, id) = text.split_once('|')?;
+        Some(Cursor {
+            created_at: DateTime::parse_from_rfc3339(created_at).ok()?,
+            id: id.parse().ok()?,
+        })
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/notes",
+    params(FilterOptions),
+    responses(
+        (status = 200, description = "List of the caller's notes"),
+        (status = 401, description = "Missing or invalid credentials"),
+        (status = 500, description = "Internal server error"),
+    ),
+    tag = "notes"
+)]
 pub async fn find_all_handler(
-    State(db): State<DatabaseConnection>,
+    State(ctx): State<Context>,
+    AuthUser { user, .. }: AuthUser,
     opts: Option<Query<FilterOptions>>,
-) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
-    let Query(_opts) = opts.unwrap_or_default();
-
-    let notes_result = Notes::find().all(&db).await.map_err(|_| {
-        let error_response = json!({
-            "status": "fail",
-            "message": "Something bad happened while fetching all note items",
-        });
-        (StatusCode::INTERNAL_SERVER_ERROR, Json(error_response))
-    })?;
-
-    let notes_list: Vec<NoteResponse> = notes_result
+) -> AppResult<impl IntoResponse> {
+    let db = ctx.db();
+    let Query(opts) = opts.unwrap_or_default();
+
+    let limit = opts
+        .limit
+        .map(|l| l as u64)
+        .unwrap_or(ctx.config.default_page_limit);
+
+    // Sort and page on `COALESCE(created_at, epoch)` so the keyset is total:
+    // rows with a null `created_at` collapse onto the epoch value and are paged
+    // through deterministically instead of being skipped by NULL comparisons.
+    let created_key = || Expr::expr(Expr::col(notes::Column::CreatedAt).if_null(epoch()));
+
+    // Only ever surface the caller's own notes.
+    let mut query = Notes::find()
+        .filter(notes::Column::OwnerId.eq(user.id))
+        .order_by_desc(created_key())
+        .order_by_desc(notes::Column::Id);
+
+    // When a cursor is supplied we resume strictly *after* the last seen row,
+    // i.e. `(created_at, id) < (cursor.created_at, cursor.id)`.
+    if let Some(raw) = opts.cursor.as_deref() {
+        let cursor =
+            Cursor::decode(raw).ok_or_else(|| AppError::Validation("Invalid cursor".to_owned()))?;
+
+        query = query.filter(
+            Condition::any()
+                .add(created_key().lt(cursor.created_at))
+                .add(
+                    Condition::all()
+                        .add(created_key().eq(cursor.created_at))
+                        .add(notes::Column::Id.lt(cursor.id)),
+                ),
+        );
+    }
+
+    // Fetch one extra row to learn whether a further page exists.
+    let mut rows = query.limit(limit + 1).all(db).await?;
+
+    let has_next = rows.len() as u64 > limit;
+    if has_next {
+        rows.pop();
+    }
+
+    // When there is a next page the cursor must always be addressable, even if
+    // the boundary row has a null `created_at`; fall back to the Unix epoch so
+    // the `(created_at, id)` keyset still points strictly before older rows.
+    let next_cursor = if has_next {
+        rows.last()
+            .map(|n| Cursor::encode(n.created_at.unwrap_or_else(epoch), n.id))
+    } else {
+        None
+    };
+
+    let notes_list: Vec<NoteResponse> = rows
         .into_iter()
         .map(|n| NoteResponse {
             id: n.id,
@@ -46,6 +147,7 @@ pub async fn find_all_handler(
         len if len > 0 => json!({
             "status": "success",
             "results": len,
+            "next_cursor": next_cursor,
             "data": notes_list
         }),
         _ => json!({
@@ -58,12 +160,36 @@ pub async fn find_all_handler(
     Ok(Json(json_response))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/notes/{id}",
+    params(("id" = Uuid, Path, description = "Note id")),
+    responses(
+        (status = 200, description = "The requested note", body = NoteResponse),
+        (status = 401, description = "Missing or invalid credentials"),
+        (status = 404, description = "Note not found"),
+        (status = 500, description = "Internal server error"),
+    ),
+    tag = "notes"
+)]
 pub async fn find_by_id_handler(
-    State(db): State<DatabaseConnection>,
+    State(ctx): State<Context>,
+    AuthUser { user, .. }: AuthUser,
     Path(id): Path<Uuid>,
-) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
-    match Notes::find_by_id(id).one(&db).await {
-        Ok(Some(note)) => {
+) -> AppResult<impl IntoResponse> {
+    let db = ctx.db();
+
+    // Serve from cache while the entry is still within its TTL, scoped to the
+    // caller so single-note reads stay private to their owner.
+    let response = match ctx.cache().get(&id, user.id) {
+        Some(cached) => cached,
+        None => {
+            let note = Notes::find_by_id(id)
+                .filter(notes::Column::OwnerId.eq(user.id))
+                .one(db)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Note with ID: {} not found", id)))?;
+
             let response = NoteResponse {
                 id,
                 title: note.title,
@@ -73,166 +199,233 @@ pub async fn find_by_id_handler(
                 created_at: note.created_at,
                 updated_at: note.updated_at,
             };
-
-            let note_response = json!({
-                "status": "success",
-                "data": {
-                    "note": response
-                }
-            });
-            Ok((StatusCode::OK, Json(note_response)))
+            ctx.cache().insert(id, user.id, response.clone());
+            response
         }
-        Ok(None) => {
-            let error_response = json!({
-                "status": "fail",
-                "message": format!("Note with ID: {} not found", id)
-            });
-            Ok((StatusCode::NOT_FOUND, Json(error_response)))
-        }
-        Err(_) => {
-            let error_response = json!({
-                "status": "fail",
-                "message": "Something went wrong while fetching the note"
-            });
-            Ok((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+    };
+
+    let note_response = json!({
+        "status": "success",
+        "data": {
+            "note": response
         }
-    }
+    });
+    Ok((StatusCode::OK, Json(note_response)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/notes",
+    request_body = CreateNoteSchema,
+    responses(
+        (status = 201, description = "Note created", body = NoteResponse),
+        (status = 401, description = "Missing or invalid credentials"),
+        (status = 409, description = "A note with those unique fields already exists"),
+        (status = 500, description = "Internal server error"),
+    ),
+    tag = "notes"
+)]
 pub async fn create_handler(
-    State(db): State<DatabaseConnection>,
+    State(ctx): State<Context>,
+    AuthUser { user, .. }: AuthUser,
     Json(data): Json<CreateNoteSchema>,
-) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+) -> AppResult<impl IntoResponse> {
+    let db = ctx.db();
     let new_note = notes::ActiveModel {
         id: NotSet,
         title: Set(data.title.clone()),
         content: Set(data.content.clone()),
         category: Set(data.category.clone()),
+        owner_id: Set(Some(user.id)),
         ..Default::default()
     };
 
-    match new_note.insert(&db).await {
-        Ok(saved_note) => {
-            let response = NoteResponse {
-                id: saved_note.id,
-                title: saved_note.title,
-                content: saved_note.content.clone(),
-                category: saved_note.category.clone(),
-                published: saved_note.published,
-                created_at: saved_note.created_at,
-                updated_at: saved_note.updated_at,
-            };
-            let note_response = json!({
-                "status": "success",
-                "data": {
-                    "note": response
-                }
-            });
-            Ok((StatusCode::CREATED, Json(note_response)))
-        }
-        Err(e) => {
-            if e.to_string()
-                .contains("duplicate key value violates unique constraint")
-            {
-                let error_response = json!({
-                    "status": "fail",
-                    "message": "Note with that title already exists",
-                });
-                Err((StatusCode::CONFLICT, Json(error_response)))
-            } else {
-                let error_response = json!({
-                    "status": "error",
-                    "message": format!("{:?}", e),
-                });
-                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
-            }
-        }
+    let saved_note = new_note.insert(db).await?;
+
+    let response = NoteResponse {
+        id: saved_note.id,
+        title: saved_note.title,
+        content: saved_note.content,
+        category: saved_note.category,
+        published: saved_note.published,
+        created_at: saved_note.created_at,
+        updated_at: saved_note.updated_at,
+    };
+    let _ = ctx.events().send(NoteEvent::Created {
+        owner_id: user.id,
+        note: response.clone(),
+    });
+
+    // Advertise the canonical URL of the new note using the configured
+    // external base URL, so clients behind a proxy get an absolute link.
+    let mut headers = HeaderMap::new();
+    if let Ok(location) = format!("{}/api/notes/{}", ctx.config.base_url, response.id).parse() {
+        headers.insert(header::LOCATION, location);
     }
+
+    let note_response = json!({
+        "status": "success",
+        "data": {
+            "note": response
+        }
+    });
+    Ok((StatusCode::CREATED, headers, Json(note_response)))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/notes/{id}",
+    params(("id" = Uuid, Path, description = "Note id")),
+    request_body = UpdateNoteSchema,
+    responses(
+        (status = 200, description = "Note updated", body = NoteResponse),
+        (status = 401, description = "Missing or invalid credentials"),
+        (status = 404, description = "Note not found"),
+        (status = 500, description = "Internal server error"),
+    ),
+    tag = "notes"
+)]
 pub async fn update_handler(
-    State(db): State<DatabaseConnection>,
+    State(ctx): State<Context>,
+    AuthUser { user, .. }: AuthUser,
     Path(id): Path<Uuid>,
     Json(data): Json<UpdateNoteSchema>,
-) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
-    let note_result = Notes::find_by_id(id).one(&db).await;
-
-    match note_result {
-        Ok(note) => {
-            let mut note: notes::ActiveModel = note.unwrap().into();
-
-            if let Some(title) = data.title {
-                note.title = Set(title);
-            }
-            if let Some(content) = data.content {
-                note.content = Set(content);
-            }
-            if let Some(category) = data.category {
-                note.category = Set(Some(category));
-            }
-            if let Some(published) = data.published {
-                note.published = Set(Some(published));
-            }
-
-            if let Ok(updated_note) = note.update(&db).await {
-                let response = NoteResponse {
-                    id: updated_note.id,
-                    title: updated_note.title.clone(),
-                    content: updated_note.content.clone(),
-                    category: updated_note.category.clone(),
-                    published: updated_note.published,
-                    created_at: updated_note.created_at,
-                    updated_at: updated_note.updated_at,
-                };
-
-                let note_response = json!({
-                    "status": "success",
-                    "data": {
-                        "note": response
-                    }
-                });
-                Ok((StatusCode::OK, Json(note_response)))
-            } else {
-                let error_response = json!({
-                    "status": "fail",
-                    "message": "Failed to update the note"
-                });
-                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
-            }
-        }
-        Err(_) => {
-            let error_response = json!({
-                "status": "error",
-                "message": format!("Error while fetching the note with ID: {}", id)
-            });
-            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
-        }
+) -> AppResult<impl IntoResponse> {
+    let db = ctx.db();
+    let note = Notes::find_by_id(id)
+        .filter(notes::Column::OwnerId.eq(user.id))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Note with ID: {} not found", id)))?;
+
+    let mut note: notes::ActiveModel = note.into();
+
+    if let Some(title) = data.title {
+        note.title = Set(title);
     }
+    if let Some(content) = data.content {
+        note.content = Set(content);
+    }
+    if let Some(category) = data.category {
+        note.category = Set(Some(category));
+    }
+    if let Some(published) = data.published {
+        note.published = Set(Some(published));
+    }
+
+    let updated_note = note.update(db).await?;
+
+    let response = NoteResponse {
+        id: updated_note.id,
+        title: updated_note.title,
+        content: updated_note.content,
+        category: updated_note.category,
+        published: updated_note.published,
+        created_at: updated_note.created_at,
+        updated_at: updated_note.updated_at,
+    };
+
+    // Refresh the cached copy so subsequent reads never serve stale data.
+    ctx.cache().insert(id, user.id, response.clone());
+
+    let _ = ctx.events().send(NoteEvent::Updated {
+        owner_id: user.id,
+        note: response.clone(),
+    });
+
+    let note_response = json!({
+        "status": "success",
+        "data": {
+            "note": response
+        }
+    });
+    Ok((StatusCode::OK, Json(note_response)))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/notes/{id}",
+    params(("id" = Uuid, Path, description = "Note id")),
+    responses(
+        (status = 204, description = "Note deleted"),
+        (status = 401, description = "Missing or invalid credentials"),
+        (status = 404, description = "Note not found"),
+        (status = 500, description = "Internal server error"),
+    ),
+    tag = "notes"
+)]
 pub async fn delete_handler(
-    State(db): State<DatabaseConnection>,
+    State(ctx): State<Context>,
+    AuthUser { user, .. }: AuthUser,
     Path(id): Path<Uuid>,
-) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
-    match Notes::delete_by_id(id).exec(&db).await {
-        Ok(rows_affected) => {
-            if rows_affected.rows_affected == 0 {
-                let error_response = json!({
-                    "status": "fail",
-                    "message": format!("Note with ID: {} not found", id),
-                });
-                return Err((StatusCode::NOT_FOUND, Json(error_response)));
-            }
-
-            Ok(StatusCode::NO_CONTENT)
-        }
-        Err(error) => {
-            let error_response = json!({
-                "status": "error",
-                "message": format!("Failed to delete note with ID: {}", id),
-                "details": error.to_string(),
-            });
-            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
-        }
+) -> AppResult<impl IntoResponse> {
+    let db = ctx.db();
+    let result = Notes::delete_many()
+        .filter(notes::Column::Id.eq(id))
+        .filter(notes::Column::OwnerId.eq(user.id))
+        .exec(db)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound(format!(
+            "Note with ID: {} not found",
+            id
+        )));
     }
+
+    // Evict the key before returning so stale data is never served.
+    ctx.cache().invalidate(&id);
+
+    let _ = ctx.events().send(NoteEvent::Deleted {
+        owner_id: user.id,
+        id,
+    });
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Report the note cache hit/miss counters.
+pub async fn cache_stats_handler(State(ctx): State<Context>) -> AppResult<impl IntoResponse> {
+    let (hits, misses) = ctx.cache().stats();
+    let body = json!({
+        "status": "success",
+        "data": {
+            "hits": hits,
+            "misses": misses
+        }
+    });
+    Ok((StatusCode::OK, Json(body)))
+}
+
+/// Stream live note changes to the caller as Server-Sent Events.
+///
+/// Subscribes to the broadcast channel and forwards each [`NoteEvent`] as a
+/// JSON SSE frame. A subscriber that falls behind and overflows the buffer is
+/// sent a `resync` hint instead of silently dropping the missed events.
+pub async fn events_handler(
+    State(ctx): State<Context>,
+    AuthUser { user, .. }: AuthUser,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let receiver = ctx.events().subscribe();
+    let owner_id = user.id;
+
+    let stream = BroadcastStream::new(receiver).filter_map(move |message| {
+        let note_event = match message {
+            // Drop changes that belong to another owner so the shared stream
+            // never leaks one user's notes to another. `Resync` is ownerless
+            // and always forwarded.
+            Ok(note_event) => match note_event.owner_id() {
+                Some(event_owner) if event_owner != owner_id => return None,
+                _ => note_event,
+            },
+            Err(_lagged) => NoteEvent::Resync,
+        };
+
+        let event = Event::default()
+            .json_data(note_event)
+            .unwrap_or_else(|_| Event::default().data("{}"));
+        Some(Ok(event))
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
 }
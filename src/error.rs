@@ -0,0 +1,69 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use sea_orm::{DbErr, SqlErr};
+use serde_json::json;
+
+/// Convenient alias for handler results that surface an [`AppError`].
+pub type AppResult<T> = Result<T, AppError>;
+
+/// Application-level error surfaced to clients as the shared
+/// `{status, message}` envelope with a matching HTTP status code.
+#[derive(Debug)]
+pub enum AppError {
+    NotFound(String),
+    Conflict(String),
+    Validation(String),
+    Unauthorized(String),
+    Database(DbErr),
+    Internal(String),
+}
+
+impl From<DbErr> for AppError {
+    fn from(err: DbErr) -> Self {
+        // Translate unique-constraint violations into a `Conflict` centrally so
+        // handlers no longer substring-match driver error strings.
+        match err.sql_err() {
+            Some(SqlErr::UniqueConstraintViolation(_)) => {
+                AppError::Conflict("Resource with those unique fields already exists".to_owned())
+            }
+            _ => AppError::Database(err),
+        }
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            AppError::NotFound(message) => (StatusCode::NOT_FOUND, message),
+            AppError::Conflict(message) => (StatusCode::CONFLICT, message),
+            AppError::Validation(message) => (StatusCode::BAD_REQUEST, message),
+            AppError::Unauthorized(message) => (StatusCode::UNAUTHORIZED, message),
+            AppError::Database(err) => {
+                // Never surface driver/SQL internals to clients; log and return
+                // a generic message.
+                eprintln!("database error: {:?}", err);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Internal server error".to_owned(),
+                )
+            }
+            AppError::Internal(message) => (StatusCode::INTERNAL_SERVER_ERROR, message),
+        };
+
+        let status_label = if status.is_server_error() {
+            "error"
+        } else {
+            "fail"
+        };
+
+        let body = json!({
+            "status": status_label,
+            "message": message,
+        });
+
+        (status, Json(body)).into_response()
+    }
+}
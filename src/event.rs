@@ -0,0 +1,37 @@
+use sea_orm::prelude::Uuid;
+use serde::Serialize;
+
+use crate::schema::NoteResponse;
+
+/// Capacity of the broadcast channel buffering note changes for SSE clients.
+pub const EVENT_CHANNEL_CAPACITY: usize = 100;
+
+/// A change to a note, fanned out to every subscribed SSE client.
+///
+/// Serializes with a `kind` discriminant so consumers can switch on the event
+/// type, e.g. `{ "kind": "created", "owner_id": "..", "note": { .. } }` or
+/// `{ "kind": "deleted", "owner_id": "..", "id": ".." }`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum NoteEvent {
+    Created { owner_id: Uuid, note: NoteResponse },
+    Updated { owner_id: Uuid, note: NoteResponse },
+    Deleted { owner_id: Uuid, id: Uuid },
+    /// Emitted to a lagging subscriber that overflowed the buffer, hinting it
+    /// to re-fetch the list rather than trust the dropped deltas.
+    Resync,
+}
+
+impl NoteEvent {
+    /// The owner a change belongs to, used to keep the shared broadcast stream
+    /// from leaking one user's notes to another. `Resync` is ownerless and is
+    /// delivered to every subscriber.
+    pub fn owner_id(&self) -> Option<Uuid> {
+        match self {
+            NoteEvent::Created { owner_id, .. }
+            | NoteEvent::Updated { owner_id, .. }
+            | NoteEvent::Deleted { owner_id, .. } => Some(*owner_id),
+            NoteEvent::Resync => None,
+        }
+    }
+}
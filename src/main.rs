@@ -1,7 +1,18 @@
 use dotenv::dotenv;
 use sea_orm::Database;
+use sea_orm_migration::MigratorTrait;
 
+use crate::config::AppConfig;
+use crate::controller::Context;
+use crate::migration::Migrator;
+
+mod auth;
+mod cache;
+mod config;
 mod controller;
+mod error;
+mod event;
+mod migration;
 mod model;
 mod schema;
 
@@ -14,11 +25,17 @@ async fn main() {
 }
 
 pub async fn run(database_uri: String) {
+    let config = AppConfig::from_env();
     let database = Database::connect(database_uri).await.unwrap();
-    let app = controller::create_routes(database).await;
+    Migrator::up(&database, None)
+        .await
+        .expect("failed to run database migrations");
+    let ctx = Context::new(database, config.clone());
+    let app = controller::create_routes(ctx).await;
 
-    println!("Listening {:<12}", 8000);
-    axum::Server::bind(&"0.0.0.0:8000".parse().unwrap())
+    let addr = config.socket_addr();
+    println!("Listening {}", addr);
+    axum::Server::bind(&addr)
         .serve(app.into_make_service())
         .await
         .unwrap();
@@ -0,0 +1,3 @@
+pub mod notes;
+pub mod sessions;
+pub mod users;
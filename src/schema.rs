@@ -1,11 +1,13 @@
 use chrono::{DateTime, FixedOffset};
 use sea_orm::prelude::Uuid;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Deserialize, Default, IntoParams)]
 pub struct FilterOptions {
     pub page: Option<usize>,
     pub limit: Option<usize>,
+    pub cursor: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -13,7 +15,19 @@ pub struct ParamOption {
     pub id: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize)]
+pub struct RegisterSchema {
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LoginSchema {
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct CreateNoteSchema {
     pub title: String,
     pub content: String,
@@ -23,7 +37,7 @@ pub struct CreateNoteSchema {
     pub published: Option<bool>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct UpdateNoteSchema {
     pub title: Option<String>,
     pub content: Option<String>,
@@ -31,13 +45,16 @@ pub struct UpdateNoteSchema {
     pub published: Option<bool>,
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Serialize, ToSchema)]
 pub struct NoteResponse {
+    #[schema(value_type = String, format = "uuid")]
     pub id: Uuid,
     pub title: String,
     pub content: String,
     pub category: Option<String>,
     pub published: Option<bool>,
+    #[schema(value_type = Option<String>, format = "date-time")]
     pub created_at: Option<DateTime<FixedOffset>>,
+    #[schema(value_type = Option<String>, format = "date-time")]
     pub updated_at: Option<DateTime<FixedOffset>>,
 }